@@ -0,0 +1,254 @@
+//! A pluggable registry mapping `fake(...)` generator names to the fake-rs
+//! calls that back them, so the set of fakers is data-driven and extensible
+//! instead of being a hand-duplicated match per template engine.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use fake::{Fake, Faker};
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde_json::Value;
+
+use crate::{Error, Result};
+
+/// Signature every registered faker must implement: draw a value from `rng`,
+/// optionally using the trailing template arguments (ranges, counts, ...).
+pub type FakerFn = Box<dyn Fn(&mut StdRng, &[Value]) -> Result<String> + Send + Sync>;
+
+/// Reads a numeric argument out of the trailing `fake(...)` varargs, erroring
+/// with a message that names the faker and the expected position.
+pub(crate) fn arg_u32(args: &[Value], idx: usize, name: &str) -> Result<u32> {
+    args.get(idx)
+        .and_then(Value::as_u64)
+        .map(|n| n as u32)
+        .ok_or_else(|| Error::Message(format!("fake('{name}', ...): expected a numeric argument at position {idx}")))
+}
+
+/// Reads a string argument out of the trailing `fake(...)` varargs, erroring
+/// with a message that names the faker and the expected position.
+pub(crate) fn arg_str<'a>(args: &'a [Value], idx: usize, name: &str) -> Result<&'a str> {
+    args.get(idx)
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::Message(format!("fake('{name}', ...): expected a string argument at position {idx}")))
+}
+
+/// Reads a `min, max` pair of numeric arguments, erroring with a message
+/// that names the faker rather than panicking the process when a template
+/// passes inverted bounds (e.g. `fake('NumberU32', 5, 1)`).
+pub(crate) fn arg_bounds(args: &[Value], min_idx: usize, max_idx: usize, name: &str) -> Result<(u32, u32)> {
+    let min = arg_u32(args, min_idx, name)?;
+    let max = arg_u32(args, max_idx, name)?;
+    if min > max {
+        return Err(Error::Message(format!("fake('{name}', ...): min ({min}) must be <= max ({max})")));
+    }
+    Ok((min, max))
+}
+
+/// Expands the name-to-faker table for a single fake-rs locale module, so the
+/// ~70-entry mapping isn't duplicated by hand for every supported locale.
+///
+/// fake-rs only ships locale-specific data for a handful of categories
+/// (address, company, internet, lorem, name, phone_number); the rest
+/// (barcode, chrono, creditcard, currency, filesystem, finance, http, job)
+/// only exist under `en`, so those stay pinned to `en` regardless of which
+/// locale table is being built.
+macro_rules! builtin_fakers_for_locale {
+    ($locale:ident) => {{
+        use fake::faker::address::$locale::*;
+        use fake::faker::barcode::en::{Isbn, Isbn10, Isbn13};
+        use fake::faker::chrono::en::{Date, DateTime, DateTimeBetween, Time};
+        use fake::faker::company::$locale::{Bs, BsAdj, BsNoun, BsVerb, Buzzword, BuzzwordMiddle, BuzzwordTail, CatchPhrase, CompanyName, CompanySuffix, Industry, Profession};
+        use fake::faker::creditcard::en::*;
+        use fake::faker::currency::en::*;
+        use fake::faker::filesystem::en::*;
+        use fake::faker::finance::en::*;
+        use fake::faker::http::en::{RfcStatusCode, ValidStatusCode};
+        use fake::faker::internet::$locale::*;
+        use fake::faker::job::en;
+        use fake::faker::job::en::*;
+        use fake::faker::lorem::$locale::*;
+        use fake::faker::name::$locale::*;
+        use fake::faker::phone_number::$locale::{CellNumber, PhoneNumber};
+
+        let mut table: HashMap<&'static str, FakerFn> = HashMap::new();
+        macro_rules! simple {
+            ($name:literal, $gen:expr) => {
+                table.insert($name, Box::new(move |rng: &mut StdRng, _args: &[Value]| Ok($gen.fake_with_rng(rng))));
+            };
+        }
+
+        simple!("CityPrefix", CityPrefix());
+        simple!("CitySuffix", CitySuffix());
+        simple!("CityName", CityName());
+        simple!("CountryName", CountryName());
+        simple!("CountryCode", CountryCode());
+        simple!("StreetSuffix", StreetSuffix());
+        simple!("StreetName", StreetName());
+        simple!("TimeZone", TimeZone());
+        simple!("StateName", StateName());
+        simple!("StateAbbr", StateAbbr());
+        simple!("SecondaryAddressType", SecondaryAddressType());
+        simple!("SecondaryAddress", SecondaryAddress());
+        simple!("ZipCode", ZipCode());
+        simple!("PostCode", PostCode());
+        simple!("BuildingNumber", BuildingNumber());
+        simple!("Latitude", Latitude());
+        simple!("Longitude", Longitude());
+        simple!("Isbn", Isbn());
+        simple!("Isbn10", Isbn10());
+        simple!("Isbn13", Isbn13());
+        simple!("CreditCardNumber", CreditCardNumber());
+        simple!("CompanySuffix", CompanySuffix());
+        simple!("CompanyName", CompanyName());
+        simple!("Buzzword", Buzzword());
+        simple!("BuzzwordMiddle", BuzzwordMiddle());
+        simple!("BuzzwordTail", BuzzwordTail());
+        simple!("CatchPhrase", CatchPhrase());
+        simple!("BsVerb", BsVerb());
+        simple!("BsAdj", BsAdj());
+        simple!("BsNoun", BsNoun());
+        simple!("Bs", Bs());
+        simple!("Profession", Profession());
+        simple!("Industry", Industry());
+        simple!("FreeEmailProvider", FreeEmailProvider());
+        simple!("DomainSuffix", DomainSuffix());
+        simple!("FreeEmail", FreeEmail());
+        simple!("SafeEmail", SafeEmail());
+        simple!("Username", Username());
+        simple!("IPv4", IPv4());
+        simple!("IPv6", IPv6());
+        simple!("IP", IP());
+        simple!("MACAddress", MACAddress());
+        simple!("UserAgent", UserAgent());
+        simple!("Seniority", Seniority());
+        simple!("Field", Field());
+        simple!("Position", Position());
+        simple!("Word", Word());
+        simple!("FirstName", FirstName());
+        simple!("LastName", LastName());
+        simple!("Title", en::Title());
+        simple!("Suffix", Suffix());
+        simple!("Name", Name());
+        simple!("NameWithTitle", NameWithTitle());
+        simple!("PhoneNumber", PhoneNumber());
+        simple!("CellNumber", CellNumber());
+        simple!("FilePath", FilePath());
+        simple!("FileName", FileName());
+        simple!("FileExtension", FileExtension());
+        simple!("DirPath", DirPath());
+        simple!("MimeType", MimeType());
+        simple!("Semver", Semver());
+        simple!("SemverStable", SemverStable());
+        simple!("SemverUnstable", SemverUnstable());
+        simple!("CurrencyCode", CurrencyCode());
+        simple!("CurrencyName", CurrencyName());
+        simple!("CurrencySymbol", CurrencySymbol());
+        simple!("Bic", Bic());
+        simple!("Isin", Isin());
+        simple!("Time", Time());
+        simple!("Date", Date());
+        simple!("DateTime", DateTime());
+        simple!("RfcStatusCode", RfcStatusCode());
+        simple!("ValidStatusCode", ValidStatusCode());
+
+        table.insert(
+            "Password",
+            Box::new(|rng: &mut StdRng, args: &[Value]| {
+                let (min, max) = arg_bounds(args, 0, 1, "Password")?;
+                Ok(Password(min as usize..max as usize).fake_with_rng(rng))
+            }),
+        );
+        table.insert(
+            "Words",
+            Box::new(|rng: &mut StdRng, args: &[Value]| {
+                let (min, max) = arg_bounds(args, 0, 1, "Words")?;
+                let words: Vec<String> = Words(min as usize..max as usize).fake_with_rng(rng);
+                Ok(words.join(" "))
+            }),
+        );
+        table.insert(
+            "Sentences",
+            Box::new(|rng: &mut StdRng, args: &[Value]| {
+                let (min, max) = arg_bounds(args, 0, 1, "Sentences")?;
+                let sentences: Vec<String> = Sentences(min as usize..max as usize).fake_with_rng(rng);
+                Ok(sentences.join(" "))
+            }),
+        );
+        table.insert(
+            "DateBetween",
+            Box::new(|rng: &mut StdRng, args: &[Value]| {
+                let parse = |s: &str| -> Result<_> {
+                    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                        .map_err(|e| Error::Message(format!("fake('DateBetween', ...): invalid date {s:?}: {e}")))
+                        .map(|d| d.and_hms_opt(0, 0, 0).expect("midnight is always valid"))
+                };
+                let start = parse(arg_str(args, 0, "DateBetween")?)?;
+                let end = parse(arg_str(args, 1, "DateBetween")?)?;
+                Ok(DateTimeBetween(start, end).fake_with_rng(rng).to_string())
+            }),
+        );
+        table.insert(
+            "NumberU32",
+            Box::new(|rng: &mut StdRng, args: &[Value]| {
+                let (min, max) = arg_bounds(args, 0, 1, "NumberU32")?;
+                Ok(rng.gen_range(min..=max).to_string())
+            }),
+        );
+
+        table
+    }};
+}
+
+/// Maps generator names to the closure that produces them, for every
+/// supported locale plus any custom fakers registered via
+/// [`crate::RRgen::register_faker`].
+pub struct Registry {
+    builtin: HashMap<&'static str, HashMap<&'static str, FakerFn>>,
+    custom: HashMap<String, FakerFn>,
+}
+
+impl Registry {
+    /// Builds the registry preloaded with every built-in faker, for every
+    /// supported locale (`en`, `zh_tw`).
+    pub fn with_builtins() -> Self {
+        let mut builtin = HashMap::new();
+        builtin.insert("en", builtin_fakers_for_locale!(en));
+        builtin.insert("zh_tw", builtin_fakers_for_locale!(zh_tw));
+        Self { builtin, custom: HashMap::new() }
+    }
+
+    /// Registers a custom faker under `name`, available regardless of the
+    /// `locale` argument passed to `fake(...)`. Shadows a built-in faker of
+    /// the same name.
+    pub fn register(&mut self, name: impl Into<String>, f: impl Fn(&mut StdRng, &[Value]) -> Result<String> + Send + Sync + 'static) {
+        self.custom.insert(name.into(), Box::new(f));
+    }
+
+    /// Whether `name` is a known built-in locale (e.g. `"en"`, `"zh_tw"`),
+    /// used to tell a `fake('Name', 'zh_tw')`-style locale argument apart
+    /// from a faker's own positional range/count arguments.
+    pub fn has_locale(&self, name: &str) -> bool {
+        self.builtin.contains_key(name)
+    }
+
+    /// Looks up `name` (custom fakers take priority, then the built-ins for
+    /// `locale`, falling back to `en`) and generates one value from `rng`.
+    /// Unknown names generate an empty string, matching the historical
+    /// behavior of the hand-written match.
+    pub fn generate(&self, name: &str, locale: Option<&str>, args: &[Value], rng: &mut StdRng) -> Result<String> {
+        if let Some(f) = self.custom.get(name) {
+            return f(rng, args);
+        }
+
+        let table = locale
+            .and_then(|locale| self.builtin.get(locale))
+            .or_else(|| self.builtin.get("en"))
+            .expect("the \"en\" locale is always registered");
+
+        match table.get(name) {
+            Some(f) => f(rng, args),
+            None => Ok(String::new()),
+        }
+    }
+}
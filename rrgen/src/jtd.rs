@@ -0,0 +1,202 @@
+//! Generates random JSON instances from a [JSON Type Definition][jtd] (JTD)
+//! schema, so a template can produce a whole realistic fixture (nested
+//! objects, arrays, tagged unions, ...) with a single `fake_schema(...)`
+//! call instead of faking one scalar at a time.
+//!
+//! [jtd]: https://jsontypedef.com/docs/jtd-in-5-minutes/
+
+use fake::faker::lorem::en::Word;
+use fake::Fake;
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde_json::{Map, Value};
+
+use crate::{Error, Result};
+
+/// Generates a random JSON instance matching `schema`, resolving any `ref`
+/// against the top-level `definitions`.
+///
+/// # Errors
+///
+/// Returns an error if `schema` is not a valid JTD schema (e.g. it isn't an
+/// object, names a primitive `type` we don't know how to generate, or
+/// references an undefined name).
+pub fn generate(schema: &Value, rng: &mut StdRng) -> Result<Value> {
+    generate_form(schema, schema, rng)
+}
+
+fn generate_form(schema: &Value, root: &Value, rng: &mut StdRng) -> Result<Value> {
+    let obj = schema
+        .as_object()
+        .ok_or_else(|| Error::Message("JTD schema must be an object".to_string()))?;
+
+    if let Some(name) = obj.get("ref").and_then(Value::as_str) {
+        let definition = root
+            .get("definitions")
+            .and_then(|definitions| definitions.get(name))
+            .ok_or_else(|| Error::Message(format!("JTD: undefined ref {name:?}")))?;
+        return generate_form(definition, root, rng);
+    }
+
+    if let Some(ty) = obj.get("type").and_then(Value::as_str) {
+        return generate_primitive(ty, rng);
+    }
+
+    if let Some(variants) = obj.get("enum").and_then(Value::as_array) {
+        if variants.is_empty() {
+            return Err(Error::Message("JTD: enum must not be empty".to_string()));
+        }
+        let index = rng.gen_range(0..variants.len());
+        return Ok(variants[index].clone());
+    }
+
+    if let Some(elements) = obj.get("elements") {
+        let len = rng.gen_range(0..=3usize);
+        let mut items = Vec::with_capacity(len);
+        for _ in 0..len {
+            items.push(generate_form(elements, root, rng)?);
+        }
+        return Ok(Value::Array(items));
+    }
+
+    if obj.contains_key("properties") || obj.contains_key("optionalProperties") {
+        let mut instance = Map::new();
+        if let Some(properties) = obj.get("properties").and_then(Value::as_object) {
+            for (name, property_schema) in properties {
+                instance.insert(name.clone(), generate_form(property_schema, root, rng)?);
+            }
+        }
+        if let Some(optional_properties) = obj.get("optionalProperties").and_then(Value::as_object) {
+            for (name, property_schema) in optional_properties {
+                if rng.gen_bool(0.5) {
+                    instance.insert(name.clone(), generate_form(property_schema, root, rng)?);
+                }
+            }
+        }
+        return Ok(Value::Object(instance));
+    }
+
+    if let Some(values_schema) = obj.get("values") {
+        let len = rng.gen_range(0..=3usize);
+        let mut instance = Map::new();
+        for _ in 0..len {
+            let key: String = Word().fake_with_rng(rng);
+            instance.insert(key, generate_form(values_schema, root, rng)?);
+        }
+        return Ok(Value::Object(instance));
+    }
+
+    if let (Some(tag), Some(mapping)) = (
+        obj.get("discriminator").and_then(Value::as_str),
+        obj.get("mapping").and_then(Value::as_object),
+    ) {
+        if mapping.is_empty() {
+            return Err(Error::Message("JTD: mapping must not be empty".to_string()));
+        }
+        let variants: Vec<&String> = mapping.keys().collect();
+        let index = rng.gen_range(0..variants.len());
+        let variant_name = variants[index].clone();
+        let variant_schema = &mapping[&variant_name];
+
+        let mut instance = generate_form(variant_schema, root, rng)?;
+        if let Value::Object(ref mut map) = instance {
+            map.insert(tag.to_string(), Value::String(variant_name));
+        }
+        return Ok(instance);
+    }
+
+    // The empty form `{}` accepts any value, so hand back a random scalar.
+    Ok(generate_any_scalar(rng))
+}
+
+fn generate_primitive(ty: &str, rng: &mut StdRng) -> Result<Value> {
+    Ok(match ty {
+        "boolean" => Value::Bool(rng.gen_bool(0.5)),
+        "string" => Value::String(Word().fake_with_rng(rng)),
+        "timestamp" => Value::String(
+            fake::faker::chrono::en::DateTime()
+                .fake_with_rng::<chrono::NaiveDateTime, _>(rng)
+                .and_utc()
+                .to_rfc3339(),
+        ),
+        "int8" => Value::from(rng.gen_range(i8::MIN..=i8::MAX)),
+        "uint8" => Value::from(rng.gen_range(u8::MIN..=u8::MAX)),
+        "int16" => Value::from(rng.gen_range(i16::MIN..=i16::MAX)),
+        "uint16" => Value::from(rng.gen_range(u16::MIN..=u16::MAX)),
+        "int32" => Value::from(rng.gen_range(i32::MIN..=i32::MAX)),
+        "uint32" => Value::from(rng.gen_range(u32::MIN..=u32::MAX)),
+        "float32" => Value::from(rng.gen_range(-1_000_000.0f32..1_000_000.0f32)),
+        "float64" => Value::from(rng.gen_range(-1_000_000.0f64..1_000_000.0f64)),
+        other => return Err(Error::Message(format!("JTD: unsupported primitive type {other:?}"))),
+    })
+}
+
+fn generate_any_scalar(rng: &mut StdRng) -> Value {
+    match rng.gen_range(0..3) {
+        0 => Value::Bool(rng.gen_bool(0.5)),
+        1 => Value::String(Word().fake_with_rng(rng)),
+        _ => Value::from(rng.gen_range(0..1000)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use serde_json::json;
+
+    use super::*;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(42)
+    }
+
+    #[test]
+    fn resolves_ref_against_root_definitions() {
+        let schema = json!({
+            "ref": "id",
+            "definitions": { "id": { "type": "uint8" } },
+        });
+
+        let instance = generate(&schema, &mut rng()).expect("ref should resolve");
+        assert!(instance.is_u64());
+    }
+
+    #[test]
+    fn errors_on_undefined_ref() {
+        let schema = json!({ "ref": "missing", "definitions": {} });
+
+        let err = generate(&schema, &mut rng()).expect_err("undefined ref should error");
+        assert!(matches!(err, Error::Message(msg) if msg.contains("undefined ref")));
+    }
+
+    #[test]
+    fn errors_on_empty_enum() {
+        let schema = json!({ "enum": [] });
+
+        let err = generate(&schema, &mut rng()).expect_err("empty enum should error");
+        assert!(matches!(err, Error::Message(msg) if msg.contains("enum must not be empty")));
+    }
+
+    #[test]
+    fn discriminator_injects_tag_into_chosen_variant() {
+        let schema = json!({
+            "discriminator": "kind",
+            "mapping": {
+                "circle": { "properties": { "radius": { "type": "uint8" } } },
+            },
+        });
+
+        let instance = generate(&schema, &mut rng()).expect("discriminator should resolve");
+        let object = instance.as_object().expect("discriminator produces an object");
+        assert_eq!(object.get("kind"), Some(&Value::String("circle".to_string())));
+        assert!(object.contains_key("radius"));
+    }
+
+    #[test]
+    fn errors_on_empty_discriminator_mapping() {
+        let schema = json!({ "discriminator": "kind", "mapping": {} });
+
+        let err = generate(&schema, &mut rng()).expect_err("empty mapping should error");
+        assert!(matches!(err, Error::Message(msg) if msg.contains("mapping must not be empty")));
+    }
+}
@@ -4,8 +4,12 @@ compile_error!("You cannot enable both 'tera' and 'minijinja' at the same time."
 #[cfg(not(any(feature = "tera", feature = "minijinja")))]
 compile_error!("You must enable exactly one feature: 'tera' or 'minijinja'.");
 
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use crate::MatchPositions::{All, First, Last};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use regex::Regex;
 use serde::Deserialize;
 #[cfg(feature = "tera")]
@@ -18,6 +22,17 @@ use log::debug;
 mod tera_filters;
 #[cfg(feature = "minijinja")]
 mod minijinja_filters;
+mod fakers;
+mod jtd;
+mod manifest;
+mod plan;
+mod vars;
+mod watch;
+
+pub use manifest::{GeneratorSpec, Manifest};
+pub use plan::{DryRunFsDriver, Plan, PlannedChange};
+pub use vars::{load_vars, VarsFormat};
+pub use watch::VarsSource;
 
 
 pub trait FsDriver {
@@ -62,6 +77,29 @@ pub trait Printer {
     fn skip_exists(&self, file_to: &Path);
     fn add_file(&self, file_to: &Path);
     fn injected(&self, file_to: &Path);
+
+    /// Reports that a single rebuild triggered by `generate_watch` failed.
+    /// Called instead of propagating the error, so a user iterating on a
+    /// template sees the next good render without restarting the process.
+    fn rebuild_failed(&self, template: &Path, err: &Error) {
+        eprintln!("rebuild of {template:?} failed: {err}");
+    }
+
+    /// Streams the output of a post-generation hook command (the `after`
+    /// front matter field), e.g. a `rustfmt`/`prettier` run on the file just
+    /// written.
+    fn hook_output(&self, command: &str, stdout: &[u8], stderr: &[u8]) {
+        println!("hook `{command}`:");
+        std::io::Write::write_all(&mut std::io::stdout(), stdout).ok();
+        std::io::Write::write_all(&mut std::io::stderr(), stderr).ok();
+    }
+
+    /// Reports the before/after content of a file `generate`/`plan` writes or
+    /// injects into: just before a generated file is written, and just after
+    /// an injection is written. The default does
+    /// nothing; `RRgen::plan`'s internal printer overrides it to build a
+    /// unified diff.
+    fn diff(&self, _path: &Path, _before: &str, _after: &str) {}
 }
 pub struct ConsolePrinter {}
 impl Printer for ConsolePrinter {
@@ -98,6 +136,12 @@ struct FrontMatter {
 
     #[serde(default)]
     injections: Option<Vec<Injection>>,
+
+    /// Shell commands to run, in order, against the generated file after it
+    /// is written (e.g. `rustfmt {}` or `prettier --write {}`). `{}` is
+    /// replaced with the file's path; commands run in `working_dir`.
+    #[serde(default)]
+    after: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, Default, Clone)]
@@ -105,6 +149,18 @@ struct Injection {
     into: String,
     content: String,
 
+    /// Stable id for an idempotent "managed region". When set, `content` is
+    /// wrapped in `<comment> <rrgen:{block}>` / `<comment> </rrgen:{block}>`
+    /// marker lines; re-running the injection replaces everything between an
+    /// existing pair of markers instead of appending a duplicate block.
+    #[serde(default)]
+    block: Option<String>,
+
+    /// Comment syntax used for the `block` marker lines (e.g. `"//"`, `"#"`).
+    /// Defaults to `"//"`.
+    #[serde(default)]
+    comment: Option<String>,
+
     #[serde(default)]
     inline: bool,
 
@@ -172,6 +228,10 @@ pub enum Error {
     #[error(transparent)]
     YAML(#[from] serde_yaml::Error),
     #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error(transparent)]
+    Ron(#[from] ron::de::SpannedError),
+    #[error(transparent)]
     Glob(#[from] glob::PatternError),
     #[error(transparent)]
     Any(Box<dyn std::error::Error + Send + Sync>),
@@ -222,6 +282,19 @@ pub struct RRgen {
     working_dir: Option<PathBuf>,
     fs: Box<dyn FsDriver>,
     printer: Box<dyn Printer>,
+    /// Shared RNG used by the `fake` template function/filter so that, given the
+    /// same seed, a whole render produces byte-for-byte reproducible output.
+    rng: Arc<Mutex<StdRng>>,
+    /// Values already produced by `fake_unique`, keyed by generator name, reset
+    /// at the start of every `generate`/`generate_by_template_with_name` call.
+    unique: Arc<Mutex<HashMap<String, HashSet<String>>>>,
+    /// Name -> generator lookup backing `fake`/`fake_unique`/`fake_schema`,
+    /// extensible at runtime via [`RRgen::register_faker`].
+    fakers: Arc<Mutex<fakers::Registry>>,
+    /// Set on the instance built by [`RRgen::plan`]: suppresses post-generation
+    /// hooks, since they shell out against a file that was never actually
+    /// written.
+    dry_run: bool,
     #[cfg(feature = "tera")]
     tera: Tera,
     #[cfg(feature = "minijinja")]
@@ -230,29 +303,114 @@ pub struct RRgen {
 
 impl Default for RRgen {
     fn default() -> Self {
+        Self::new_with_rng(StdRng::from_entropy())
+    }
+}
+
+impl RRgen {
+    /// Builds an [`RRgen`] instance around an already-seeded RNG, registering
+    /// it with whichever template engine is enabled so the `fake` function
+    /// shares a single source of randomness for the whole instance.
+    fn new_with_rng(rng: StdRng) -> Self {
+        let rng = Arc::new(Mutex::new(rng));
+        let unique = Arc::new(Mutex::new(HashMap::new()));
+        let fakers = Arc::new(Mutex::new(fakers::Registry::with_builtins()));
+
         #[cfg(feature = "tera")]
         let mut tera_instance = Tera::default();
         #[cfg(feature = "tera")]
-        tera_filters::register_all(&mut tera_instance);
+        tera_filters::register_all(&mut tera_instance, rng.clone(), unique.clone(), fakers.clone());
 
         #[cfg(feature = "minijinja")]
         let mut minijinja = Environment::new();
         #[cfg(feature = "minijinja")]
-        minijinja_filters::register_all(&mut minijinja);
+        minijinja_filters::register_all(&mut minijinja, rng.clone(), unique.clone(), fakers.clone());
 
         Self {
             working_dir: None,
             fs: Box::new(RealFsDriver {}),
             printer: Box::new(ConsolePrinter {}),
+            rng,
+            unique,
+            fakers,
+            dry_run: false,
             #[cfg(feature = "tera")]
             tera: tera_instance,
             #[cfg(feature = "minijinja")]
             minijinja,
         }
     }
-}
 
-impl RRgen {
+    /// Computes what `generate` would do against `input` and `vars` without
+    /// writing anything to disk: which files would be added, overwritten,
+    /// injected into, or skipped, each with a unified diff against its
+    /// current content (if any).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if operation fails, same as
+    /// `generate`.
+    pub fn plan(&self, input: &str, vars: &serde_json::Value) -> Result<plan::Plan> {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+
+        // Snapshot the RNG rather than sharing it: `generate` draws from and
+        // advances it, and a "non-mutating" plan must not leave a real,
+        // subsequent `generate` drawing different `fake()`/`uuid()` values
+        // than what was just previewed. The template engines are cloned and
+        // re-registered against the snapshot so their `fake`/`uuid`/... closures
+        // (which closed over the original `Arc<Mutex<StdRng>>` at construction
+        // time) draw from it instead.
+        let rng = Arc::new(Mutex::new(self.rng.lock().expect("fake rng lock poisoned").clone()));
+
+        #[cfg(feature = "tera")]
+        let mut tera = self.tera.clone();
+        #[cfg(feature = "tera")]
+        tera_filters::register_all(&mut tera, rng.clone(), self.unique.clone(), self.fakers.clone());
+
+        #[cfg(feature = "minijinja")]
+        let mut minijinja = self.minijinja.clone();
+        #[cfg(feature = "minijinja")]
+        minijinja_filters::register_all(&mut minijinja, rng.clone(), self.unique.clone(), self.fakers.clone());
+
+        let planner = Self {
+            working_dir: self.working_dir.clone(),
+            fs: Box::new(plan::DryRunFsDriver::default()),
+            printer: Box::new(plan::PlanPrinter::new(changes.clone())),
+            rng,
+            unique: self.unique.clone(),
+            fakers: self.fakers.clone(),
+            dry_run: true,
+            #[cfg(feature = "tera")]
+            tera,
+            #[cfg(feature = "minijinja")]
+            minijinja,
+        };
+
+        let result = planner.generate(input, vars)?;
+        let changes = Arc::try_unwrap(changes)
+            .expect("plan printer should not outlive RRgen::plan")
+            .into_inner()
+            .expect("plan state lock poisoned");
+        let message = match result {
+            GenResult::Generated { message } => message,
+            GenResult::Skipped => None,
+        };
+
+        Ok(plan::Plan { changes, message })
+    }
+
+    /// Registers a custom fake-data generator under `name`, available from
+    /// templates as `fake('{name}')` / `fake_unique('{name}')` regardless of
+    /// the `locale` argument. Lets downstream users add generators (ULID,
+    /// UUID v4/v7, business-specific IDs, ...) without forking `RRgen`.
+    pub fn register_faker(
+        &self,
+        name: impl Into<String>,
+        f: impl Fn(&mut StdRng, &[serde_json::Value]) -> Result<String> + Send + Sync + 'static,
+    ) {
+        self.fakers.lock().expect("faker registry lock poisoned").register(name, f);
+    }
+
     /// Creates a new [`RRgen`] instance with the specified working directory.
     ///
     /// # Example
@@ -270,6 +428,21 @@ impl RRgen {
         }
     }
 
+    /// Creates a new [`RRgen`] instance whose `fake(...)` calls are driven by
+    /// a `StdRng` seeded from `seed`, so the same template and seed always
+    /// produce the same generated data (city names, emails, etc.) across runs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rrgen::RRgen;
+    ///
+    /// let rgen = RRgen::with_seed(42);
+    /// ```
+    #[must_use]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::new_with_rng(StdRng::seed_from_u64(seed))
+    }
+
     /// Creates a new `RRgen` instance with the specified templates.
     ///
     /// # Example
@@ -310,6 +483,7 @@ impl RRgen {
     pub fn generate(&self, input: &str, vars: &serde_json::Value) -> Result<GenResult> {
         debug!("generating from template: {input:?}");
         debug!("template vars: {:?}", serde_json::to_string(&vars)?);
+        self.reset_unique();
         #[cfg(feature = "tera")]{
             let mut tera = self.tera.clone();
             let rendered = tera.render_str(input, &Context::from_serialize(vars.clone())?)?;
@@ -322,6 +496,20 @@ impl RRgen {
         }
     }
 
+    /// Generate from a template contained in `input`, parsing `vars_str`
+    /// according to `format` instead of requiring pre-parsed JSON. Lets
+    /// callers keep their generator inputs in whatever serialization their
+    /// project already uses (YAML, TOML, RON, ...).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `vars_str` fails to parse as
+    /// `format`, or if generating fails.
+    pub fn generate_from_str(&self, input: &str, vars_str: &str, format: VarsFormat) -> Result<GenResult> {
+        let vars = format.parse(vars_str)?;
+        self.generate(input, &vars)
+    }
+
     /// Generate from a template added in the template engine given by `name`
     ///
     /// # Errors
@@ -329,6 +517,7 @@ impl RRgen {
     /// This function will return an error if operation fails
     pub fn generate_by_template_with_name(&self, name: &str, vars: &serde_json::Value) -> Result<GenResult> {
         debug!("generating from template with name: {name:?}, vars: {:?}",serde_json::to_string(&vars)?);
+        self.reset_unique();
 
         #[cfg(feature = "tera")]{
             let rendered = self.tera.render(name, &Context::from_serialize(vars.clone())?)?;
@@ -343,6 +532,26 @@ impl RRgen {
         }
     }
 
+    /// Clears the `fake_unique` dedupe state so each top-level render starts
+    /// with a clean slate, rather than carrying uniqueness constraints over
+    /// from a previous `generate`/`generate_by_template_with_name` call.
+    fn reset_unique(&self) {
+        self.unique.lock().expect("unique state lock poisoned").clear();
+    }
+
+    /// Watches `templates` and `vars` for changes and regenerates on every
+    /// change, debouncing bursts of filesystem events into a single rebuild.
+    /// Runs until the process is interrupted. Errors from a single rebuild
+    /// are reported via [`Printer::rebuild_failed`] rather than returned, so
+    /// one bad template doesn't stop the loop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying filesystem watcher can't be set up.
+    pub fn generate_watch(&self, templates: &[PathBuf], vars: VarsSource) -> Result<()> {
+        watch::run(self, templates, vars)
+    }
+
     /// Add template with the given name in template engine
     ///
     /// # Errors
@@ -407,13 +616,20 @@ impl RRgen {
                 }
             }
 
-            if self.fs.exists(&path_to) {
+            let existing = self.fs.exists(&path_to);
+            let before = if existing { self.fs.read_file(&path_to)? } else { String::new() };
+            if existing {
                 self.printer.overwrite_file(&path_to);
             } else {
                 self.printer.add_file(&path_to);
             }
+            self.printer.diff(&path_to, &before, body);
+
             // write main file
             self.fs.write_file(&path_to, &body)?;
+
+            // run post-generation hooks (e.g. formatters) against it
+            self.run_hooks(&frontmatter.after, &path_to)?;
         }
 
         // handle injects
@@ -423,6 +639,49 @@ impl RRgen {
         })
     }
 
+    /// Runs each front matter `after` command against `file`, substituting
+    /// `{}` with its path, in `working_dir` (if set). Streams output through
+    /// [`Printer::hook_output`] and errors out on a non-zero exit. A no-op
+    /// on the dry-run instance built by `RRgen::plan`, since the file it
+    /// would run against was never actually written.
+    ///
+    /// `file` is already joined with `working_dir`, but the command itself
+    /// is also run with `working_dir` as its `current_dir`, so `{}` is
+    /// substituted with the path relative to `working_dir` to avoid
+    /// double-prefixing it (e.g. `working_dir/working_dir/src/foo.rs`).
+    fn run_hooks(&self, after: &[String], file: &Path) -> Result<()> {
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let relative_file = self
+            .working_dir
+            .as_ref()
+            .and_then(|working_dir| file.strip_prefix(working_dir).ok())
+            .unwrap_or(file);
+
+        for command_template in after {
+            let command_str = command_template.replace("{}", &relative_file.to_string_lossy());
+
+            let mut command = std::process::Command::new("sh");
+            command.arg("-c").arg(&command_str);
+            if let Some(working_dir) = &self.working_dir {
+                command.current_dir(working_dir);
+            }
+
+            let output = command.output()?;
+            self.printer.hook_output(&command_str, &output.stdout, &output.stderr);
+
+            if !output.status.success() {
+                return Err(Error::Message(format!(
+                    "hook `{command_str}` exited with {}",
+                    output.status,
+                )));
+            }
+        }
+        Ok(())
+    }
+
     fn handle_injects(&self, injections: Option<Vec<Injection>>, message: Option<String>) -> Result<GenResult> {
         if let Some(injections) = injections {
             for injection in &injections {
@@ -446,7 +705,9 @@ impl RRgen {
                     }
                 }
 
-                let new_content = if injection.prepend {
+                let new_content = if let Some(block_id) = &injection.block {
+                    apply_block_injection(&file_content, &injection, block_id)?
+                } else if injection.prepend {
                     format!("{content}\n{file_content}")
                 } else if injection.append {
                     format!("{file_content}\n{content}")
@@ -483,6 +744,7 @@ impl RRgen {
 
                 self.fs.write_file(&injection_to, &new_content)?;
                 self.printer.injected(&injection_to);
+                self.printer.diff(&injection_to, &file_content, &new_content);
             }
         }
         Ok(GenResult::Generated {
@@ -490,6 +752,72 @@ impl RRgen {
         })
     }
 }
+/// Applies an idempotent "managed region" injection: if a `<rrgen:{block_id}>`
+/// / `</rrgen:{block_id}>` marker pair already exists in `file_content`, the
+/// content between them is replaced (preserving the opening marker's
+/// indentation); otherwise the wrapped block is inserted using the
+/// injection's existing `before`/`after`/`append` positioning.
+///
+/// # Errors
+///
+/// Returns an error if a start marker is found without a matching end marker.
+fn apply_block_injection(file_content: &str, injection: &Injection, block_id: &str) -> Result<String> {
+    let comment = injection.comment.as_deref().unwrap_or("//");
+    let start_marker = format!("{comment} <rrgen:{block_id}>");
+    let end_marker = format!("{comment} </rrgen:{block_id}>");
+
+    let lines: Vec<&str> = file_content.lines().collect();
+    let start_line = lines.iter().position(|line| line.trim() == start_marker);
+
+    let Some(start_line) = start_line else {
+        let wrapped = format!("{start_marker}\n{}\n{end_marker}", injection.content);
+        return Ok(insert_wrapped_block(file_content, injection, &wrapped));
+    };
+
+    let end_line = lines[start_line + 1..]
+        .iter()
+        .position(|line| line.trim() == end_marker)
+        .map(|offset| start_line + 1 + offset)
+        .ok_or_else(|| {
+            Error::Message(format!(
+                "found start marker for region {block_id:?} but no matching end marker `{end_marker}`"
+            ))
+        })?;
+
+    let indent = &lines[start_line][..lines[start_line].len() - lines[start_line].trim_start().len()];
+    let mut new_lines: Vec<String> = lines[..start_line].iter().map(|line| (*line).to_string()).collect();
+    new_lines.push(format!("{indent}{start_marker}"));
+    new_lines.extend(injection.content.lines().map(|line| format!("{indent}{line}")));
+    new_lines.push(format!("{indent}{end_marker}"));
+    new_lines.extend(lines[end_line + 1..].iter().map(|line| (*line).to_string()));
+    Ok(new_lines.join("\n"))
+}
+
+/// Inserts a managed-region `wrapped` block (markers + content) at the
+/// position described by `injection`'s `before`/`after`/`prepend`/`append`
+/// fields, mirroring the positioning rules used for plain injections.
+fn insert_wrapped_block(file_content: &str, injection: &Injection, wrapped: &str) -> String {
+    if injection.prepend {
+        format!("{wrapped}\n{file_content}")
+    } else if injection.append {
+        format!("{file_content}\n{wrapped}")
+    } else if let Some(before) = &injection.before {
+        insert_content_at_positions(file_content, wrapped, injection.inline, before, First, InsertionPoint::Before)
+    } else if let Some(before_last) = &injection.before_last {
+        insert_content_at_positions(file_content, wrapped, injection.inline, before_last, Last, InsertionPoint::Before)
+    } else if let Some(before_all) = &injection.before_all {
+        insert_content_at_positions(file_content, wrapped, injection.inline, before_all, All, InsertionPoint::Before)
+    } else if let Some(after) = &injection.after {
+        insert_content_at_positions(file_content, wrapped, injection.inline, after, First, InsertionPoint::After)
+    } else if let Some(after_last) = &injection.after_last {
+        insert_content_at_positions(file_content, wrapped, injection.inline, after_last, Last, InsertionPoint::After)
+    } else if let Some(after_all) = &injection.after_all {
+        insert_content_at_positions(file_content, wrapped, injection.inline, after_all, All, InsertionPoint::After)
+    } else {
+        format!("{file_content}\n{wrapped}")
+    }
+}
+
 #[derive(Debug, Clone)]
 enum MatchPositions {
     All,
@@ -561,4 +889,52 @@ fn insert_content_at_positions(
         }
     }).collect::<Vec<String>>();
     new_lines.join("\n")
+}
+
+#[cfg(test)]
+mod block_injection_tests {
+    use super::*;
+
+    fn injection(content: &str) -> Injection {
+        Injection { into: "target".to_string(), content: content.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn inserts_wrapped_block_when_no_marker_exists() {
+        let injection = Injection { append: true, ..injection("hello();") };
+
+        let result = apply_block_injection("line one\nline two", &injection, "greeting").unwrap();
+
+        assert_eq!(result, "line one\nline two\n// <rrgen:greeting>\nhello();\n// </rrgen:greeting>");
+    }
+
+    #[test]
+    fn replaces_existing_block_and_preserves_indentation() {
+        let file = "fn main() {\n    // <rrgen:greeting>\n    old();\n    // </rrgen:greeting>\n}";
+        let injection = injection("new();");
+
+        let result = apply_block_injection(file, &injection, "greeting").unwrap();
+
+        assert_eq!(result, "fn main() {\n    // <rrgen:greeting>\n    new();\n    // </rrgen:greeting>\n}");
+    }
+
+    #[test]
+    fn errors_when_end_marker_is_missing() {
+        let file = "// <rrgen:greeting>\nold();";
+        let injection = injection("new();");
+
+        let err = apply_block_injection(file, &injection, "greeting").expect_err("missing end marker should error");
+
+        assert!(matches!(err, Error::Message(msg) if msg.contains("no matching end marker")));
+    }
+
+    #[test]
+    fn honors_custom_comment_syntax() {
+        let file = "# <rrgen:greeting>\nold\n# </rrgen:greeting>";
+        let injection = Injection { comment: Some("#".to_string()), ..injection("new") };
+
+        let result = apply_block_injection(file, &injection, "greeting").unwrap();
+
+        assert_eq!(result, "# <rrgen:greeting>\nnew\n# </rrgen:greeting>");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,140 @@
+//! Manifest-driven orchestration of several named generators, run in
+//! dependency order so a generator that injects into a file produced by
+//! another always runs after it.
+
+use std::collections::VecDeque;
+
+use crate::{Error, GenResult, RRgen, Result};
+
+/// One named generator in a [`Manifest`]. `name` must match a template
+/// already added to the `RRgen` instance (e.g. via `with_templates`), and
+/// `needs` lists the names of generators that must run before this one.
+#[derive(Debug, Clone)]
+pub struct GeneratorSpec {
+    pub name: String,
+    pub needs: Vec<String>,
+}
+
+/// A set of generators to run together, resolved into dependency order by
+/// [`RRgen::generate_all`].
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    pub generators: Vec<GeneratorSpec>,
+}
+
+impl RRgen {
+    /// Runs every generator in `manifest` against `vars`, in an order that
+    /// satisfies each generator's `needs`, and merges their `GenResult`
+    /// messages into one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `needs` names an unknown generator, if the
+    /// dependencies form a cycle, or if any individual generator fails.
+    pub fn generate_all(&self, manifest: &Manifest, vars: &serde_json::Value) -> Result<GenResult> {
+        let order = topological_order(&manifest.generators)?;
+
+        let mut messages = Vec::new();
+        for &index in &order {
+            let spec = &manifest.generators[index];
+            if let GenResult::Generated { message: Some(message) } = self.generate_by_template_with_name(&spec.name, vars)? {
+                messages.push(message);
+            }
+        }
+
+        Ok(GenResult::Generated { message: Some(messages.join("\n")) })
+    }
+}
+
+/// Resolves `generators` into an execution order where every generator
+/// comes after everything it `needs`, using Kahn's algorithm. Returns
+/// indices into `generators` rather than the specs themselves, to keep
+/// ordering and ownership separate.
+fn topological_order(generators: &[GeneratorSpec]) -> Result<Vec<usize>> {
+    let index_of = generators
+        .iter()
+        .enumerate()
+        .map(|(index, generator)| (generator.name.as_str(), index))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    let mut in_degree = vec![0usize; generators.len()];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); generators.len()];
+
+    for (index, generator) in generators.iter().enumerate() {
+        for need in &generator.needs {
+            let &dep_index = index_of.get(need.as_str()).ok_or_else(|| {
+                Error::Message(format!("generator {:?} needs unknown generator {need:?}", generator.name))
+            })?;
+            in_degree[index] += 1;
+            dependents[dep_index].push(index);
+        }
+    }
+
+    let mut queue: VecDeque<usize> = (0..generators.len()).filter(|&i| in_degree[i] == 0).collect();
+    let mut order = Vec::with_capacity(generators.len());
+
+    while let Some(index) = queue.pop_front() {
+        order.push(index);
+        for &dependent in &dependents[index] {
+            in_degree[dependent] -= 1;
+            if in_degree[dependent] == 0 {
+                queue.push_back(dependent);
+            }
+        }
+    }
+
+    if order.len() != generators.len() {
+        let cycle: Vec<&str> = (0..generators.len())
+            .filter(|&i| in_degree[i] > 0)
+            .map(|i| generators[i].name.as_str())
+            .collect();
+        return Err(Error::Message(format!("cannot order generators, cycle among: {}", cycle.join(", "))));
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str, needs: &[&str]) -> GeneratorSpec {
+        GeneratorSpec { name: name.to_string(), needs: needs.iter().map(|s| s.to_string()).collect() }
+    }
+
+    fn name_order(generators: &[GeneratorSpec], order: &[usize]) -> Vec<&str> {
+        order.iter().map(|&i| generators[i].name.as_str()).collect()
+    }
+
+    #[test]
+    fn orders_dependents_after_their_dependencies() {
+        let generators = vec![spec("migration", &[]), spec("model", &["migration"]), spec("controller", &["model"])];
+
+        let order = topological_order(&generators).expect("acyclic graph should resolve");
+        assert_eq!(name_order(&generators, &order), vec!["migration", "model", "controller"]);
+    }
+
+    #[test]
+    fn independent_generators_keep_their_declared_order() {
+        let generators = vec![spec("a", &[]), spec("b", &[]), spec("c", &[])];
+
+        let order = topological_order(&generators).expect("no dependencies should resolve");
+        assert_eq!(name_order(&generators, &order), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn errors_on_unknown_dependency() {
+        let generators = vec![spec("model", &["missing"])];
+
+        let err = topological_order(&generators).expect_err("unknown need should error");
+        assert!(matches!(err, Error::Message(msg) if msg.contains("unknown generator \"missing\"")));
+    }
+
+    #[test]
+    fn errors_on_cycle() {
+        let generators = vec![spec("a", &["b"]), spec("b", &["a"])];
+
+        let err = topological_order(&generators).expect_err("cycle should error");
+        assert!(matches!(err, Error::Message(msg) if msg.contains("cycle among")));
+    }
+}
@@ -1,32 +1,66 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use cruet::string::pluralize;
 use heck::{ToKebabCase, ToLowerCamelCase, ToPascalCase, ToSnakeCase};
-use minijinja::Environment;
-use fake::{Dummy, Fake, Faker};
-use fake::faker::address::en::{*};
-use fake::faker::*;
-use fake::faker::barcode::en::{Isbn, Isbn10, Isbn13};
-use fake::faker::chrono::en::{Date, DateTime, Time};
-use fake::faker::company::en::{Bs, BsAdj, BsNoun, BsVerb, Buzzword, BuzzwordMiddle, BuzzwordTail, CatchPhrase, CompanyName, CompanySuffix, Industry, Profession};
-use fake::faker::creditcard::en::{*};
-use fake::faker::currency::en::*;
-use fake::faker::filesystem::en::{*};
-use fake::faker::finance::en::*;
-use fake::faker::http::en::{RfcStatusCode, ValidStatusCode};
-use fake::faker::internet::en::{*};
-use fake::faker::job::en::*;
-use fake::faker::lorem::en::*;
-use fake::faker::name::en::*;
-use fake::faker::phone_number::en::{CellNumber, PhoneNumber};
+use minijinja::value::{Kwargs, Rest, Value as MiniValue};
+use minijinja::{Environment, Error as MiniJinjaError, ErrorKind};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::fakers::Registry;
 
 /// Registers all available filters for a given `Minijinja` environment.
-pub fn register_all(env: &mut Environment) {
+///
+/// `rng` is shared with the rest of the `RRgen` instance so that, given the
+/// same seed, the `fake`/`fake_unique`/`uuid`/`random_int`/`fake_name`/
+/// `fake_email`/`shuffle` functions produce the same sequence of values
+/// across a whole render. `unique` is the per-render dedupe state backing
+/// `fake_unique`, and `fakers` is the name -> generator registry.
+pub fn register_all(
+    env: &mut Environment,
+    rng: Arc<Mutex<StdRng>>,
+    unique: Arc<Mutex<HashMap<String, HashSet<String>>>>,
+    fakers: Arc<Mutex<Registry>>,
+) {
     env.add_filter("snake_case", snake_case);
     env.add_filter("camel_case", camel_case);
     env.add_filter("kebab_case", kebab_case);
     env.add_filter("pascal_case", pascal_case);
     env.add_filter("lower_camel_case", lower_camel_case);
     env.add_filter("plural", plural);
-    env.add_function("fake", fake);
+    env.add_function("fake", {
+        let rng = rng.clone();
+        let fakers = fakers.clone();
+        move |value: String, args: Rest<MiniValue>, kwargs: Kwargs| fake(value, args.0, kwargs, &rng, &fakers)
+    });
+    env.add_function("fake_schema", {
+        let rng = rng.clone();
+        move |schema: MiniValue| fake_schema(&schema, &rng)
+    });
+    env.add_function("fake_unique", {
+        let rng = rng.clone();
+        let fakers = fakers.clone();
+        move |value: String, args: Rest<MiniValue>, kwargs: Kwargs| fake_unique(value, args.0, kwargs, &rng, &unique, &fakers)
+    });
+    env.add_function("uuid", {
+        let rng = rng.clone();
+        move || uuid(&rng)
+    });
+    env.add_function("random_int", {
+        let rng = rng.clone();
+        move |min: i64, max: i64| random_int(min, max, &rng)
+    });
+    env.add_function("fake_name", {
+        let rng = rng.clone();
+        let fakers = fakers.clone();
+        move || fake_name(&rng, &fakers)
+    });
+    env.add_function("fake_email", {
+        let rng = rng.clone();
+        let fakers = fakers.clone();
+        move || fake_email(&rng, &fakers)
+    });
+    env.add_filter("shuffle", move |list: Vec<MiniValue>| shuffle(list, &rng));
 }
 
 pub fn snake_case(value: String) -> String {
@@ -53,82 +87,175 @@ pub fn plural(value: String) -> String {
     pluralize::to_plural(&value)
 }
 
-pub fn fake(value: String) -> String {
-    match value.as_str() {
-        "CityPrefix" => CityPrefix().fake(),
-        "CitySuffix"=> CitySuffix().fake(),
-        "CityName"=> CityName().fake(),
-        "CountryName"=> CountryName().fake(),
-        "CountryCode"=> CountryCode().fake(),
-        "StreetSuffix"=> StreetSuffix().fake(),
-        "StreetName"=> StreetName().fake(),
-        "TimeZone"=> TimeZone().fake(),
-        "StateName"=> StateName().fake(),
-        "StateAbbr"=> StateAbbr().fake(),
-        "SecondaryAddressType"=> SecondaryAddressType().fake(),
-        "SecondaryAddress"=> SecondaryAddress().fake(),
-        "ZipCode"=> ZipCode().fake(),
-        "PostCode"=> PostCode().fake(),
-        "BuildingNumber"=> BuildingNumber().fake(),
-        "Latitude"=> Latitude().fake(),
-        "Longitude"=> Longitude().fake(),
-        "Isbn"=> Isbn().fake(),
-        "Isbn10"=> Isbn10().fake(),
-        "Isbn13"=> Isbn13().fake(),
-        "CreditCardNumber"=> CreditCardNumber().fake(),
-        "CompanySuffix"=> CompanySuffix().fake(),
-        "CompanyName"=> CompanyName().fake(),
-        "Buzzword"=> Buzzword().fake(),
-        "BuzzwordMiddle"=> BuzzwordMiddle().fake(),
-        "BuzzwordTail"=> BuzzwordTail().fake(),
-        "CatchPhrase"=> CatchPhrase().fake(),
-        "BsVerb"=> BsVerb().fake(),
-        "BsAdj"=> BsAdj().fake(),
-        "BsNoun"=> BsNoun().fake(),
-        "Bs"=> Bs().fake(),
-        "Profession"=> Profession().fake(),
-        "Industry"=> Industry().fake(),
-        "FreeEmailProvider"=> FreeEmailProvider().fake(),
-        "DomainSuffix"=> DomainSuffix().fake(),
-        "FreeEmail"=> FreeEmail().fake(),
-        "SafeEmail"=> SafeEmail().fake(),
-        "Username"=> Username().fake(),
-        "Password"=> Password(1..10).fake(),
-        "IPv4"=> IPv4().fake(),
-        "IPv6"=> IPv6().fake(),
-        "IP"=> IP().fake(),
-        "MACAddress"=> MACAddress().fake(),
-        "UserAgent"=> UserAgent().fake(),
-        "Seniority"=> Seniority().fake(),
-        "Field"=> Field().fake(),
-        "Position"=> Position().fake(),
-        "Word"=> Word().fake(),
-        "FirstName"=> FirstName().fake(),
-        "LastName"=> LastName().fake(),
-        "Title"=> job::en::Title().fake(),
-        "Suffix"=> Suffix().fake(),
-        "Name"=> Name().fake(),
-        "NameWithTitle"=> NameWithTitle().fake(),
-        "PhoneNumber"=> PhoneNumber().fake(),
-        "CellNumber"=> CellNumber().fake(),
-        "FilePath"=> FilePath().fake(),
-        "FileName"=> FileName().fake(),
-        "FileExtension"=> FileExtension().fake(),
-        "DirPath"=> DirPath().fake(),
-        "MimeType"=> MimeType().fake(),
-        "Semver"=> Semver().fake(),
-        "SemverStable"=> SemverStable().fake(),
-        "SemverUnstable"=> SemverUnstable().fake(),
-        "CurrencyCode"=> CurrencyCode().fake(),
-        "CurrencyName"=> CurrencyName().fake(),
-        "CurrencySymbol"=> CurrencySymbol().fake(),
-        "Bic"=> Bic().fake(),
-        "Isin"=> Isin().fake(),
-        "Time"=> Time().fake(),
-        "Date"=> Date().fake(),
-        "DateTime"=> DateTime().fake(),
-        "RfcStatusCode"=> RfcStatusCode().fake(),
-        "ValidStatusCode"=> ValidStatusCode().fake(),
-        _ => "".to_string()
+/// Generates a fake value for the named faker, drawing from the shared,
+/// possibly-seeded `rng` instead of the thread-local RNG so that a whole
+/// render can be made byte-for-byte reproducible.
+///
+/// If the first positional argument is a string naming a known locale, it
+/// selects the fake-rs locale module to draw from (e.g.
+/// `fake('Name', 'zh_tw')`), falling back to `"en"` when omitted or
+/// unrecognized. Any remaining positional arguments are forwarded to fakers
+/// that accept ranges or counts, e.g. `fake('Password', 8, 16)` or
+/// `fake('Words', 3, 7)`.
+pub fn fake(
+    value: String,
+    args: Vec<MiniValue>,
+    kwargs: Kwargs,
+    rng: &Arc<Mutex<StdRng>>,
+    fakers: &Arc<Mutex<Registry>>,
+) -> std::result::Result<String, MiniJinjaError> {
+    kwargs.assert_all_used()?;
+
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    let fakers = fakers.lock().expect("faker registry lock poisoned");
+    let (locale, args) = split_locale_arg(&args, &fakers);
+    let args = to_json_args(args);
+
+    fakers
+        .generate(&value, locale, &args, &mut rng)
+        .map_err(|e| MiniJinjaError::new(ErrorKind::InvalidOperation, e.to_string()))
+}
+
+/// Like `fake`, but keeps retrying (up to `MAX_UNIQUE_ATTEMPTS` times) until
+/// it produces a value not yet seen for this generator name during the
+/// current render, making it suitable for columns like email/username that
+/// must be unique across the generated rows. Errors if the space is
+/// exhausted before a new value is found.
+pub fn fake_unique(
+    value: String,
+    args: Vec<MiniValue>,
+    kwargs: Kwargs,
+    rng: &Arc<Mutex<StdRng>>,
+    unique: &Arc<Mutex<HashMap<String, HashSet<String>>>>,
+    fakers: &Arc<Mutex<Registry>>,
+) -> std::result::Result<String, MiniJinjaError> {
+    const MAX_UNIQUE_ATTEMPTS: usize = 1000;
+
+    kwargs.assert_all_used()?;
+
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    let fakers = fakers.lock().expect("faker registry lock poisoned");
+    let (locale, args) = split_locale_arg(&args, &fakers);
+    let args = to_json_args(args);
+    let mut unique = unique.lock().expect("fake_unique state lock poisoned");
+    let seen = unique.entry(value.clone()).or_default();
+
+    for _ in 0..MAX_UNIQUE_ATTEMPTS {
+        let candidate = fakers
+            .generate(&value, locale, &args, &mut rng)
+            .map_err(|e| MiniJinjaError::new(ErrorKind::InvalidOperation, e.to_string()))?;
+        if seen.insert(candidate.clone()) {
+            return Ok(candidate);
+        }
+    }
+
+    Err(MiniJinjaError::new(
+        ErrorKind::InvalidOperation,
+        format!("fake_unique('{value}', ...): exhausted {MAX_UNIQUE_ATTEMPTS} attempts without finding a new value"),
+    ))
+}
+
+/// If `args[0]` is a string naming a known fake-rs locale (e.g.
+/// `fake('Name', 'zh_tw')`), treats it as the locale and returns the
+/// remaining args; otherwise every arg is forwarded to the faker itself
+/// (e.g. `fake('Words', 3, 7)`), and the locale defaults to `"en"`.
+fn split_locale_arg<'a>(args: &'a [MiniValue], fakers: &Registry) -> (Option<&'a str>, &'a [MiniValue]) {
+    match args.first().and_then(MiniValue::as_str) {
+        Some(candidate) if fakers.has_locale(candidate) => (Some(candidate), &args[1..]),
+        _ => (None, args),
+    }
+}
+
+/// Converts minijinja argument values into `serde_json::Value` so the
+/// faker registry can parse ranges/counts without depending on minijinja's
+/// own value type.
+fn to_json_args(args: &[MiniValue]) -> Vec<serde_json::Value> {
+    args.iter()
+        .map(|v| serde_json::to_value(v).unwrap_or(serde_json::Value::Null))
+        .collect()
+}
+
+/// Generates a whole random JSON instance from a JSON Type Definition
+/// `schema`, driven by the shared `rng`, so a template can emit a realistic
+/// nested fixture in one call instead of faking one scalar at a time.
+pub fn fake_schema(schema: &MiniValue, rng: &Arc<Mutex<StdRng>>) -> std::result::Result<MiniValue, MiniJinjaError> {
+    let schema = serde_json::to_value(schema).map_err(|e| {
+        MiniJinjaError::new(ErrorKind::InvalidOperation, format!("fake_schema(...): invalid schema: {e}"))
+    })?;
+
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    let instance = crate::jtd::generate(&schema, &mut rng)
+        .map_err(|e| MiniJinjaError::new(ErrorKind::InvalidOperation, e.to_string()))?;
+
+    Ok(MiniValue::from_serialize(&instance))
+}
+
+/// Generates a random v4 UUID string, drawn from the shared `rng` so it
+/// takes part in a seeded, reproducible render like every other `fake*`
+/// helper.
+pub fn uuid(rng: &Arc<Mutex<StdRng>>) -> String {
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    let mut bytes = [0u8; 16];
+    rng.fill(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    format!(
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        bytes[0],
+        bytes[1],
+        bytes[2],
+        bytes[3],
+        bytes[4],
+        bytes[5],
+        bytes[6],
+        bytes[7],
+        bytes[8],
+        bytes[9],
+        bytes[10],
+        bytes[11],
+        bytes[12],
+        bytes[13],
+        bytes[14],
+        bytes[15],
+    )
+}
+
+/// Draws an integer from `min..=max` using the shared `rng`.
+pub fn random_int(min: i64, max: i64, rng: &Arc<Mutex<StdRng>>) -> std::result::Result<i64, MiniJinjaError> {
+    if min > max {
+        return Err(MiniJinjaError::new(ErrorKind::InvalidOperation, format!("random_int({min}, {max}): min must be <= max")));
+    }
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    Ok(rng.gen_range(min..=max))
+}
+
+/// Shorthand for `fake('Name')`, for templates that just want a quick
+/// reproducible name without spelling out the generator name.
+pub fn fake_name(rng: &Arc<Mutex<StdRng>>, fakers: &Arc<Mutex<Registry>>) -> std::result::Result<String, MiniJinjaError> {
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    let fakers = fakers.lock().expect("faker registry lock poisoned");
+    fakers
+        .generate("Name", None, &[], &mut rng)
+        .map_err(|e| MiniJinjaError::new(ErrorKind::InvalidOperation, e.to_string()))
+}
+
+/// Shorthand for `fake('SafeEmail')`, for templates that just want a quick
+/// reproducible email without spelling out the generator name.
+pub fn fake_email(rng: &Arc<Mutex<StdRng>>, fakers: &Arc<Mutex<Registry>>) -> std::result::Result<String, MiniJinjaError> {
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    let fakers = fakers.lock().expect("faker registry lock poisoned");
+    fakers
+        .generate("SafeEmail", None, &[], &mut rng)
+        .map_err(|e| MiniJinjaError::new(ErrorKind::InvalidOperation, e.to_string()))
+}
+
+/// Shuffles `list` in place (Fisher-Yates) using the shared `rng`, so the
+/// result is reproducible across runs with the same seed.
+pub fn shuffle(mut list: Vec<MiniValue>, rng: &Arc<Mutex<StdRng>>) -> Vec<MiniValue> {
+    let mut rng = rng.lock().expect("fake rng lock poisoned");
+    for i in (1..list.len()).rev() {
+        let j = rng.gen_range(0..=i);
+        list.swap(i, j);
     }
+    list
 }
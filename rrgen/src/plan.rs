@@ -0,0 +1,128 @@
+//! A non-mutating "dry run" mode, built on the existing [`FsDriver`] /
+//! [`Printer`] seams: [`RRgen::plan`] runs the same generation pipeline as
+//! `generate`, but against a [`DryRunFsDriver`] that never touches disk and
+//! a printer that records what would have happened instead of printing it.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use similar::TextDiff;
+
+use crate::{FsDriver, Printer, RealFsDriver, Result};
+
+/// An [`FsDriver`] that discards writes instead of performing them. It keeps
+/// no record of the discarded content: the would-be changes are captured by
+/// the plan's printer, not by this driver. Reads and existence checks still
+/// hit the real filesystem, so a file added earlier in the same plan is not
+/// seen by later reads or injections.
+pub struct DryRunFsDriver {
+    inner: RealFsDriver,
+}
+
+impl Default for DryRunFsDriver {
+    fn default() -> Self {
+        Self { inner: RealFsDriver {} }
+    }
+}
+
+impl FsDriver for DryRunFsDriver {
+    fn write_file(&self, _path: &Path, _content: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn read_file(&self, path: &Path) -> Result<String> {
+        self.inner.read_file(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.inner.exists(path)
+    }
+}
+
+/// One change `RRgen::plan` found it would make. `diff` is a unified diff
+/// against the file's current content, filled in once the corresponding
+/// [`Printer::diff`] call lands; it's empty for `Skipped`, which never
+/// writes anything.
+#[derive(Debug, Clone)]
+pub enum PlannedChange {
+    Added { path: PathBuf, diff: String },
+    Overwritten { path: PathBuf, diff: String },
+    Injected { path: PathBuf, diff: String },
+    Skipped { path: PathBuf },
+}
+
+impl PlannedChange {
+    fn path(&self) -> &Path {
+        match self {
+            Self::Added { path, .. } | Self::Overwritten { path, .. } | Self::Injected { path, .. } | Self::Skipped { path } => path,
+        }
+    }
+
+    fn set_diff(&mut self, text: String) {
+        match self {
+            Self::Added { diff, .. } | Self::Overwritten { diff, .. } | Self::Injected { diff, .. } => *diff = text,
+            Self::Skipped { .. } => {}
+        }
+    }
+}
+
+/// The structured summary returned by [`RRgen::plan`]: every file that would
+/// be added, overwritten, injected into, or skipped, plus the top-level
+/// front matter `message`, in the same order `generate` would process them.
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    pub changes: Vec<PlannedChange>,
+    pub message: Option<String>,
+}
+
+/// The [`Printer`] used internally by [`RRgen::plan`]: turns the usual
+/// add/overwrite/inject/skip notifications, plus the before/after content
+/// from [`Printer::diff`], into a [`Plan`].
+pub(crate) struct PlanPrinter {
+    changes: Arc<Mutex<Vec<PlannedChange>>>,
+}
+
+impl PlanPrinter {
+    pub(crate) fn new(changes: Arc<Mutex<Vec<PlannedChange>>>) -> Self {
+        Self { changes }
+    }
+
+    fn push(&self, change: PlannedChange) {
+        self.changes.lock().expect("plan state lock poisoned").push(change);
+    }
+}
+
+impl Printer for PlanPrinter {
+    fn overwrite_file(&self, file_to: &Path) {
+        self.push(PlannedChange::Overwritten { path: file_to.to_path_buf(), diff: String::new() });
+    }
+
+    fn skip_exists(&self, file_to: &Path) {
+        self.push(PlannedChange::Skipped { path: file_to.to_path_buf() });
+    }
+
+    fn add_file(&self, file_to: &Path) {
+        self.push(PlannedChange::Added { path: file_to.to_path_buf(), diff: String::new() });
+    }
+
+    fn injected(&self, file_to: &Path) {
+        self.push(PlannedChange::Injected { path: file_to.to_path_buf(), diff: String::new() });
+    }
+
+    fn diff(&self, path: &Path, before: &str, after: &str) {
+        let text = unified_diff(path, before, after);
+        let mut changes = self.changes.lock().expect("plan state lock poisoned");
+        if let Some(change) = changes.iter_mut().rev().find(|change| change.path() == path) {
+            change.set_diff(text);
+        }
+    }
+}
+
+/// Renders a unified diff of `before` -> `after`, headered with `path`.
+fn unified_diff(path: &Path, before: &str, after: &str) -> String {
+    let path = path.to_string_lossy();
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .header(&path, &path)
+        .to_string()
+}
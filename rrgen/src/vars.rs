@@ -0,0 +1,66 @@
+//! Loading template variables from multiple serialization formats, all
+//! normalized to the internal `serde_json::Value` every template engine
+//! consumes.
+
+use std::path::Path;
+
+use crate::{Error, Result};
+
+/// Serialization format a variables source can be written in. Every variant
+/// is parsed into the same internal `serde_json::Value` before being handed
+/// to a template, so the format is only a matter of what's convenient for
+/// whoever is producing the vars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarsFormat {
+    Json,
+    Yaml,
+    Toml,
+    Ron,
+}
+
+impl VarsFormat {
+    /// Infers a format from a file extension (`json`, `yaml`/`yml`, `toml`,
+    /// `ron`), matched case-insensitively.
+    #[must_use]
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "ron" => Some(Self::Ron),
+            _ => None,
+        }
+    }
+
+    /// Parses `input` according to this format into the `serde_json::Value`
+    /// the template engines consume.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` isn't valid for this format.
+    pub fn parse(self, input: &str) -> Result<serde_json::Value> {
+        match self {
+            Self::Json => Ok(serde_json::from_str(input)?),
+            Self::Yaml => Ok(serde_yaml::from_str(input)?),
+            Self::Toml => Ok(serde_json::to_value(toml::from_str::<toml::Value>(input)?)?),
+            Self::Ron => Ok(serde_json::to_value(ron::de::from_str::<ron::Value>(input)?)?),
+        }
+    }
+}
+
+/// Reads and parses a variables file at `path`, inferring its [`VarsFormat`]
+/// from the file extension.
+///
+/// # Errors
+///
+/// Returns an error if the extension is unrecognized, the file can't be
+/// read, or its contents don't parse as that format.
+pub fn load_vars(path: &Path) -> Result<serde_json::Value> {
+    let format = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(VarsFormat::from_extension)
+        .ok_or_else(|| Error::Message(format!("cannot infer vars format from extension of {path:?}")))?;
+    let content = fs_err::read_to_string(path)?;
+    format.parse(&content)
+}
@@ -0,0 +1,78 @@
+//! Watch-and-regenerate mode: re-renders a set of templates whenever they
+//! (or their variables source) change on disk, so a user iterating on a
+//! template sees the next good render without restarting the process.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::{Error, RRgen, Result};
+
+/// How `generate_watch` should load the variables passed to each rebuild.
+pub enum VarsSource {
+    /// Re-read and re-parse a file before every rebuild, inferring its
+    /// format (JSON, YAML, TOML, or RON) from its extension.
+    File(PathBuf),
+    /// Call a closure to produce fresh variables before every rebuild.
+    Closure(Box<dyn Fn() -> Result<serde_json::Value> + Send + Sync>),
+}
+
+impl VarsSource {
+    fn load(&self) -> Result<serde_json::Value> {
+        match self {
+            Self::File(path) => crate::vars::load_vars(path),
+            Self::Closure(f) => f(),
+        }
+    }
+
+    fn watched_path(&self) -> Option<&Path> {
+        match self {
+            Self::File(path) => Some(path.as_path()),
+            Self::Closure(_) => None,
+        }
+    }
+}
+
+/// How long to wait after a filesystem event before rebuilding, coalescing
+/// any further events that arrive in the meantime into the same rebuild.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+pub(crate) fn run(rrgen: &RRgen, templates: &[PathBuf], vars: VarsSource) -> Result<()> {
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .map_err(|e| Error::Any(Box::new(e)))?;
+
+    for path in templates.iter().chain(vars.watched_path()) {
+        let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+        watcher.watch(dir, RecursiveMode::NonRecursive).map_err(|e| Error::Any(Box::new(e)))?;
+    }
+
+    rebuild_all(rrgen, templates, &vars);
+
+    while rx.recv().is_ok() {
+        // Coalesce any further events in the debounce window into this rebuild.
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+        rebuild_all(rrgen, templates, &vars);
+    }
+
+    Ok(())
+}
+
+fn rebuild_all(rrgen: &RRgen, templates: &[PathBuf], vars: &VarsSource) {
+    for template in templates {
+        if let Err(err) = rebuild_one(rrgen, template, vars) {
+            rrgen.printer.rebuild_failed(template, &err);
+        }
+    }
+}
+
+fn rebuild_one(rrgen: &RRgen, template: &Path, vars: &VarsSource) -> Result<()> {
+    let vars = vars.load()?;
+    let input = rrgen.fs.read_file(template)?;
+    rrgen.generate(&input, &vars)?;
+    Ok(())
+}